@@ -0,0 +1,14 @@
+//! Differential target: decodes the same bytes with the zero-copy view
+//! and with the `prost`-generated reference types, and asserts the two
+//! parses agree field-by-field. See [`example_module_fuzz::fuzz_differential`]
+//! for why this catches bugs the roundtrip target in `roundtrip.rs` can't.
+
+use honggfuzz::fuzz;
+
+fn main() {
+    loop {
+        fuzz!(|data: &[u8]| {
+            example_module_fuzz::fuzz_differential(data);
+        });
+    }
+}
@@ -0,0 +1,20 @@
+//! Coverage-guided persistent-mode fuzzing of the zero-copy decoder.
+//!
+//! `fuzz!` below drives the same `HF_ITER`-style persistent loop honggfuzz
+//! uses for its C harnesses: one process stays resident and is fed a new
+//! input on every iteration, so coverage feedback accumulates across
+//! millions of inputs instead of being reset by a re-exec per test case.
+//! Build with `HFUZZ_BUILD_ARGS="-Z sanitizer=address" cargo hfuzz build`
+//! so a pointer/offset computed from the untrusted buffer that escapes
+//! the zero-copy view's bounds aborts immediately instead of silently
+//! reading adjacent memory.
+
+use honggfuzz::fuzz;
+
+fn main() {
+    loop {
+        fuzz!(|data: &[u8]| {
+            example_module_fuzz::fuzz(data);
+        });
+    }
+}
@@ -0,0 +1,130 @@
+//! Shared harness logic for the coverage-guided fuzz targets under
+//! `hfuzz_targets/`. Kept in a library crate so the roundtrip and
+//! differential binaries don't duplicate the field-walking logic, and so
+//! both can be exercised from a plain `#[test]` with a seed corpus during
+//! normal `cargo test` runs, without requiring `cargo hfuzz`.
+
+use example_module::cosmos::bank::v1beta1::{Metadata, MsgMultiSend, MsgSend, Params};
+use example_module::cosmos::base::v1beta1::{Coin, DecCoin};
+use zeropb::{FieldView, MessageView};
+
+/// Decodes `data` as every message type this crate generates, walks every
+/// field accessor (recursing into nested repeated `Coin`/`Input`/`Output`
+/// lists and `bytes`/`string` fields), re-encodes, and asserts that the
+/// re-encoded bytes decode to the same fixpoint.
+///
+/// This is the body of the persistent-mode loop: each call corresponds to
+/// one `HF_ITER` iteration, so it must not leak state across calls.
+pub fn fuzz(data: &[u8]) {
+    use example_module::cosmos::bank::v1beta1::{
+        DenomUnit, Input, MsgBurn, MsgBurnResponse, MsgMultiSendResponse, MsgSendResponse,
+        MsgSetSendEnabled, MsgSetSendEnabledResponse, MsgUpdateParams, MsgUpdateParamsResponse,
+        Output, SendEnabled, Supply,
+    };
+    use example_module::test1::{Greet, GreetResponse};
+
+    fuzz_one::<Greet>(data);
+    fuzz_one::<GreetResponse>(data);
+    fuzz_one::<Coin>(data);
+    fuzz_one::<DecCoin>(data);
+    fuzz_one::<Params>(data);
+    fuzz_one::<SendEnabled>(data);
+    fuzz_one::<Input>(data);
+    fuzz_one::<Output>(data);
+    fuzz_one::<Supply>(data);
+    fuzz_one::<DenomUnit>(data);
+    fuzz_one::<Metadata>(data);
+    fuzz_one::<MsgSend>(data);
+    fuzz_one::<MsgSendResponse>(data);
+    fuzz_one::<MsgMultiSend>(data);
+    fuzz_one::<MsgMultiSendResponse>(data);
+    fuzz_one::<MsgUpdateParams>(data);
+    fuzz_one::<MsgUpdateParamsResponse>(data);
+    fuzz_one::<MsgSetSendEnabled>(data);
+    fuzz_one::<MsgSetSendEnabledResponse>(data);
+    fuzz_one::<MsgBurn>(data);
+    fuzz_one::<MsgBurnResponse>(data);
+}
+
+fn fuzz_one<M: MessageView>(data: &[u8]) {
+    let Ok(view) = M::decode(data) else {
+        return;
+    };
+    walk_fields(&view);
+
+    let reencoded = view.encode_to_vec();
+    let Ok(view2) = M::decode(&reencoded) else {
+        panic!("message failed to decode after a successful first decode");
+    };
+    walk_fields(&view2);
+
+    assert_eq!(
+        reencoded,
+        view2.encode_to_vec(),
+        "decode -> encode -> decode did not reach a fixpoint for {}",
+        M::FULL_NAME,
+    );
+}
+
+/// Touches every accessor reachable from `view`, including nested messages
+/// and `bytes`/`string` fields, so AddressSanitizer catches an
+/// out-of-bounds read in the zero-copy layer even when the top-level
+/// decode itself reports success.
+fn walk_fields<M: MessageView>(view: &M) {
+    for field in view.fields() {
+        match field {
+            FieldView::Scalar(_) => {}
+            FieldView::Bytes(bytes) => {
+                std::hint::black_box(bytes.iter().fold(0u8, |acc, &b| acc ^ b));
+            }
+            FieldView::String(s) => {
+                std::hint::black_box(s.bytes().fold(0u8, |acc, b| acc ^ b));
+            }
+            FieldView::Message(nested) => walk_fields(&nested),
+            FieldView::Repeated(items) => {
+                for item in items {
+                    walk_fields(&item);
+                }
+            }
+        }
+    }
+}
+
+/// Decodes `data` with this crate's zero-copy view and with the
+/// `prost`-generated reference type, and asserts the two parses agree
+/// field-by-field. A decoder can pass the roundtrip fixpoint in [`fuzz`]
+/// while still parsing some input differently from a reference
+/// implementation; this catches that whenever both decoders accept the
+/// input. Inputs that only one of the two decoders accepts are not
+/// reported.
+pub fn fuzz_differential(data: &[u8]) {
+    differential_one::<MsgSend, example_module_prost_ref::cosmos::bank::v1beta1::MsgSend>(data);
+    differential_one::<MsgMultiSend, example_module_prost_ref::cosmos::bank::v1beta1::MsgMultiSend>(
+        data,
+    );
+    differential_one::<Params, example_module_prost_ref::cosmos::bank::v1beta1::Params>(data);
+}
+
+fn differential_one<M, P>(data: &[u8])
+where
+    M: MessageView,
+    P: prost::Message + Default + PartialEq + std::fmt::Debug,
+{
+    let zero_copy = M::decode(data).ok();
+    let reference: Option<P> = prost::Message::decode(data).ok();
+    // Only a *field-value* disagreement between two decoders that both
+    // accepted `data` is a real bug: two conformant decoders routinely
+    // disagree on whether malformed input (invalid UTF-8 in a `string`
+    // field, duplicate/out-of-order fields, trailing bytes, ...) should
+    // be accepted or rejected at all, so treating an accept/reject
+    // mismatch as fatal would abort the run on the first such input
+    // instead of surfacing the divergences this target exists to find.
+    if let (Some(view), Some(reference)) = (&zero_copy, &reference) {
+        assert_eq!(
+            &view.to_prost_equivalent::<P>(),
+            reference,
+            "zero-copy and prost parses disagree for {}",
+            M::FULL_NAME,
+        );
+    }
+}
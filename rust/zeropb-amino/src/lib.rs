@@ -0,0 +1,181 @@
+//! `SIGN_MODE_LEGACY_AMINO_JSON` support for wallets and the Ledger Cosmos
+//! app that predate protobuf-based signing.
+//!
+//! Amino identifies a message by a registered string name rather than its
+//! proto full name (`cosmos.bank.v1beta1.MsgSend` signs as
+//! `{"type":"cosmos-sdk/MsgSend","value":{...}}`), and the sign-bytes are
+//! canonical JSON: object keys sorted lexicographically at every nesting
+//! level, fields at their Go zero value omitted entirely (`omitempty`),
+//! and integers encoded as JSON strings rather than numbers so a 64-bit
+//! amount never round-trips through a lossy JSON-number parser.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+/// Maps a fully-qualified proto message name to its registered Amino type
+/// name.
+#[derive(Default, Clone)]
+pub struct AminoTypeRegistry {
+    names: BTreeMap<&'static str, &'static str>,
+}
+
+impl AminoTypeRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, full_name: &'static str, amino_name: &'static str) -> &mut Self {
+        self.names.insert(full_name, amino_name);
+        self
+    }
+
+    pub fn amino_name(&self, full_name: &str) -> Option<&'static str> {
+        self.names.get(full_name).copied()
+    }
+}
+
+/// The registry entries for the bank messages whose proto descriptors
+/// this crate's sibling modules compile; the Ledger Cosmos app and most
+/// wallets still expect exactly these names.
+pub fn bank_amino_registry() -> AminoTypeRegistry {
+    let mut registry = AminoTypeRegistry::new();
+    registry
+        .register("cosmos.bank.v1beta1.MsgSend", "cosmos-sdk/MsgSend")
+        .register("cosmos.bank.v1beta1.MsgMultiSend", "cosmos-sdk/MsgMultiSend")
+        .register("cosmos.bank.v1beta1.MsgUpdateParams", "cosmos-sdk/x/bank/MsgUpdateParams")
+        .register("cosmos.bank.v1beta1.MsgSetSendEnabled", "cosmos-sdk/MsgSetSendEnabled");
+    registry
+}
+
+/// A message value in Amino's data model, already reduced to JSON-shaped
+/// terms. Generated Amino sign-bytes code builds one of these per message
+/// rather than going through `serde_json::Value`, so that (a) integers
+/// are `IntString` instead of an easily-mishandled JSON number, and (b) a
+/// zero-value field is simply never inserted into an [`AminoValue::Object`]
+/// rather than relying on a `skip_serializing_if` attribute per field.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AminoValue {
+    Bool(bool),
+    String(String),
+    /// Encodes as a JSON string. Used for every integer field, matching
+    /// Amino's legacy encoding of `(u)int64`-ish values.
+    IntString(String),
+    Array(Vec<AminoValue>),
+    /// Keys are sorted lexicographically when encoded, regardless of
+    /// insertion order, because this is a `BTreeMap`.
+    Object(BTreeMap<String, AminoValue>),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AminoEncodeError {
+    #[error("{0:?} has no registered Amino type name")]
+    UnregisteredType(String),
+}
+
+/// Produces the canonical Amino JSON sign-bytes for a message of type
+/// `full_name`, wrapping `value` as `{"type": amino_name, "value": value}`.
+pub fn sign_bytes_json(
+    registry: &AminoTypeRegistry,
+    full_name: &str,
+    value: AminoValue,
+) -> Result<Vec<u8>, AminoEncodeError> {
+    let amino_name = registry
+        .amino_name(full_name)
+        .ok_or_else(|| AminoEncodeError::UnregisteredType(full_name.to_string()))?;
+
+    let mut wrapped = BTreeMap::new();
+    wrapped.insert("type".to_string(), AminoValue::String(amino_name.to_string()));
+    wrapped.insert("value".to_string(), value);
+
+    let mut out = String::new();
+    encode_canonical(&AminoValue::Object(wrapped), &mut out);
+    Ok(out.into_bytes())
+}
+
+fn encode_canonical(value: &AminoValue, out: &mut String) {
+    match value {
+        AminoValue::Bool(b) => {
+            out.push_str(if *b { "true" } else { "false" });
+        }
+        AminoValue::String(s) | AminoValue::IntString(s) => {
+            encode_json_string(s, out);
+        }
+        AminoValue::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                encode_canonical(item, out);
+            }
+            out.push(']');
+        }
+        AminoValue::Object(fields) => {
+            out.push('{');
+            // `BTreeMap` iterates in key order, giving the sorted-keys
+            // requirement for free at every nesting level.
+            for (i, (key, value)) in fields.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                encode_json_string(key, out);
+                out.push(':');
+                encode_canonical(value, out);
+            }
+            out.push('}');
+        }
+    }
+}
+
+/// Escapes a string the way Go's `encoding/json` does by default (which
+/// is what produces the real Amino sign-bytes this crate must match
+/// byte-for-byte): besides the usual JSON escapes, `<`, `>`, and `&` are
+/// written as JSON unicode escapes of U+003C, U+003E and U+0026 for
+/// HTML-embedding safety, and
+/// U+2028/U+2029 are escaped because they are valid JSON whitespace but
+/// invalid inside a JavaScript string literal. Skipping any of these
+/// produces sign-bytes the Ledger Cosmos app and legacy wallets compute
+/// differently, so the signature they produce wouldn't verify.
+fn encode_json_string(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '<' => out.push_str("\\u003c"),
+            '>' => out.push_str("\\u003e"),
+            '&' => out.push_str("\\u0026"),
+            '\u{2028}' => out.push_str("\\u2028"),
+            '\u{2029}' => out.push_str("\\u2029"),
+            c if (c as u32) < 0x20 => {
+                write!(out, "\\u{:04x}", c as u32).unwrap();
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Returns `true` if `value` is the Go zero value for its shape and
+/// should therefore be omitted from its parent [`AminoValue::Object`]
+/// entirely (`omitempty` semantics): an empty string, `false`, an empty
+/// array, or an empty object.
+///
+/// `IntString` is intentionally never considered empty at `"0"` --
+/// Amino's `omitempty` only applies to Go's untagged zero value for the
+/// *Go* field type, and integer fields carrying a meaningful zero (e.g.
+/// an `amount` of zero) are still emitted; only fields the generated code
+/// chooses not to populate are left out of the `Object` in the first
+/// place.
+pub fn is_amino_empty(value: &AminoValue) -> bool {
+    match value {
+        AminoValue::Bool(b) => !b,
+        AminoValue::String(s) => s.is_empty(),
+        AminoValue::IntString(_) => false,
+        AminoValue::Array(items) => items.is_empty(),
+        AminoValue::Object(fields) => fields.is_empty(),
+    }
+}
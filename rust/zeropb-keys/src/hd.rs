@@ -0,0 +1,164 @@
+//! BIP32 hierarchical-deterministic derivation over secp256k1, and the
+//! BIP39 mnemonic -> seed step that feeds it, scoped to the path Cosmos
+//! wallets use.
+//!
+//! Cosmos's registered SLIP-44 coin type is `118`, so the standard
+//! recovery path is `m/44'/118'/0'/0/0` ([`COSMOS_HD_PATH`]) rather than
+//! Bitcoin's `m/44'/0'/...`. Account/address-index segments beyond the
+//! first are supported via [`ExtendedKey::derive_path`] for wallets that
+//! manage multiple Cosmos accounts from one seed.
+
+use bip39::Mnemonic;
+use hmac::{Hmac, Mac};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::elliptic_curve::PrimeField;
+use k256::{ecdsa::SigningKey, Scalar};
+use sha2::Sha512;
+
+/// The path every Cosmos SDK wallet derives its default account from.
+pub const COSMOS_HD_PATH: &str = "m/44'/118'/0'/0/0";
+
+const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+#[derive(Debug, thiserror::Error)]
+pub enum HdError {
+    #[error("HD path must start with 'm' and use '/'-separated segments, got {0:?}")]
+    MalformedPath(String),
+    #[error("HD path segment {0:?} is not a valid index")]
+    MalformedSegment(String),
+    #[error("derived child key is invalid (probability ~2^-127); caller should retry with the next index")]
+    InvalidChildKey,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChildIndex {
+    Normal(u32),
+    Hardened(u32),
+}
+
+/// A BIP32 extended private key: a 32-byte secp256k1 scalar plus the
+/// 32-byte chain code used to derive its children.
+#[derive(Clone)]
+pub struct ExtendedKey {
+    private_key: [u8; 32],
+    chain_code: [u8; 32],
+}
+
+impl ExtendedKey {
+    /// Derives the BIP32 master key from a BIP39 mnemonic, per
+    /// `seed = PBKDF2-HMAC-SHA512(mnemonic, "mnemonic" || passphrase, 2048)`
+    /// (performed by [`Mnemonic::to_seed`]) followed by
+    /// `HMAC-SHA512("Bitcoin seed", seed)`.
+    pub fn master(mnemonic: &Mnemonic, passphrase: &str) -> Self {
+        Self::master_from_seed(&mnemonic.to_seed(passphrase))
+    }
+
+    pub fn master_from_seed(seed: &[u8]) -> Self {
+        let mut mac =
+            Hmac::<Sha512>::new_from_slice(b"Bitcoin seed").expect("HMAC accepts any key length");
+        mac.update(seed);
+        let out = mac.finalize().into_bytes();
+
+        let mut private_key = [0u8; 32];
+        let mut chain_code = [0u8; 32];
+        private_key.copy_from_slice(&out[..32]);
+        chain_code.copy_from_slice(&out[32..]);
+        Self {
+            private_key,
+            chain_code,
+        }
+    }
+
+    /// Derives the descendant key at `path`, e.g. [`COSMOS_HD_PATH`].
+    pub fn derive_path(&self, path: &str) -> Result<Self, HdError> {
+        let mut key = self.clone();
+        for segment in parse_path(path)? {
+            key = key.derive_child(segment)?;
+        }
+        Ok(key)
+    }
+
+    /// Derives a single child key per BIP32 CKDpriv.
+    pub fn derive_child(&self, index: ChildIndex) -> Result<Self, HdError> {
+        let mut mac = Hmac::<Sha512>::new_from_slice(&self.chain_code)
+            .expect("HMAC accepts any key length");
+        match index {
+            ChildIndex::Hardened(i) => {
+                // Hardened derivation hashes the private key itself (with
+                // a 0x00 prefix to keep it the same width as a compressed
+                // public key), so a hardened child can't be derived from
+                // the parent's public key alone.
+                mac.update(&[0]);
+                mac.update(&self.private_key);
+                mac.update(&(i | HARDENED_OFFSET).to_be_bytes());
+            }
+            ChildIndex::Normal(i) => {
+                let point = self.signing_key().verifying_key().to_encoded_point(true);
+                mac.update(point.as_bytes());
+                mac.update(&i.to_be_bytes());
+            }
+        }
+        let out = mac.finalize().into_bytes();
+        let (il, chain_code) = out.split_at(32);
+
+        let parent_scalar = parent_scalar(&self.private_key);
+        let il_scalar = Scalar::from_repr((*il).into());
+        let il_scalar: Scalar = Option::from(il_scalar).ok_or(HdError::InvalidChildKey)?;
+
+        let child_scalar = il_scalar + parent_scalar;
+        if bool::from(using_zero(&child_scalar)) {
+            return Err(HdError::InvalidChildKey);
+        }
+
+        let mut private_key = [0u8; 32];
+        private_key.copy_from_slice(&child_scalar.to_bytes());
+        let mut chain_code_out = [0u8; 32];
+        chain_code_out.copy_from_slice(chain_code);
+
+        Ok(Self {
+            private_key,
+            chain_code: chain_code_out,
+        })
+    }
+
+    pub fn signing_key(&self) -> SigningKey {
+        SigningKey::from_bytes((&self.private_key).into())
+            .expect("a validly-derived BIP32 scalar is always a valid signing key")
+    }
+
+    pub fn chain_code(&self) -> [u8; 32] {
+        self.chain_code
+    }
+}
+
+fn parent_scalar(bytes: &[u8; 32]) -> Scalar {
+    Option::from(Scalar::from_repr((*bytes).into()))
+        .expect("parent key was itself validated on construction")
+}
+
+fn using_zero(scalar: &Scalar) -> subtle::Choice {
+    scalar.is_zero()
+}
+
+fn parse_path(path: &str) -> Result<Vec<ChildIndex>, HdError> {
+    let mut segments = path.split('/');
+    if segments.next() != Some("m") {
+        return Err(HdError::MalformedPath(path.to_string()));
+    }
+    segments
+        .map(|segment| {
+            let (digits, hardened) = match segment.strip_suffix('\'').or_else(|| segment.strip_suffix('h')) {
+                Some(digits) => (digits, true),
+                None => (segment, false),
+            };
+            let index: u32 = digits
+                .parse()
+                .map_err(|_| HdError::MalformedSegment(segment.to_string()))?;
+            Ok(if hardened {
+                ChildIndex::Hardened(index)
+            } else {
+                ChildIndex::Normal(index)
+            })
+        })
+        .collect()
+}
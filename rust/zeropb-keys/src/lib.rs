@@ -0,0 +1,129 @@
+//! Key management for signing the bank messages defined in this
+//! workspace: importing/exporting secp256k1 and ed25519 keys from the
+//! PKCS8 DER/PEM interchange format other tools generate keys in, and
+//! deriving keys from a BIP39 mnemonic along a Cosmos BIP44 path.
+//!
+//! Cosmos account keys are secp256k1 by default, but validator consensus
+//! keys are ed25519, so both curves are supported here rather than
+//! hard-coding one.
+
+pub mod hd;
+
+use pkcs8::{DecodePrivateKey, EncodePrivateKey, LineEnding};
+
+/// A signing key for one of the two curves Cosmos uses.
+pub enum SigningKey {
+    Secp256k1(k256::ecdsa::SigningKey),
+    Ed25519(ed25519_dalek::SigningKey),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyCurve {
+    Secp256k1,
+    Ed25519,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum KeyImportError {
+    #[error("failed to parse PKCS8 key: {0}")]
+    Pkcs8(#[from] pkcs8::Error),
+    #[error("failed to parse PEM: {0}")]
+    Pem(#[from] pkcs8::der::pem::Error),
+}
+
+impl SigningKey {
+    /// Imports a key from a PKCS8 DER blob, the format most external
+    /// tools (OpenSSL, HSMs, other wallets) export private keys in.
+    pub fn from_pkcs8_der(der: &[u8], curve: KeyCurve) -> Result<Self, KeyImportError> {
+        Ok(match curve {
+            KeyCurve::Secp256k1 => {
+                Self::Secp256k1(k256::ecdsa::SigningKey::from_pkcs8_der(der)?)
+            }
+            KeyCurve::Ed25519 => {
+                Self::Ed25519(ed25519_dalek::SigningKey::from_pkcs8_der(der)?)
+            }
+        })
+    }
+
+    /// Imports a key from a PEM-encoded PKCS8 document (`-----BEGIN
+    /// PRIVATE KEY-----`).
+    pub fn from_pkcs8_pem(pem: &str, curve: KeyCurve) -> Result<Self, KeyImportError> {
+        Ok(match curve {
+            KeyCurve::Secp256k1 => {
+                Self::Secp256k1(k256::ecdsa::SigningKey::from_pkcs8_pem(pem)?)
+            }
+            KeyCurve::Ed25519 => {
+                Self::Ed25519(ed25519_dalek::SigningKey::from_pkcs8_pem(pem)?)
+            }
+        })
+    }
+
+    /// Builds a signing key from a BIP32 extended key derived from a
+    /// mnemonic, e.g. via `ExtendedKey::master(mnemonic, "").derive_path(COSMOS_HD_PATH)`.
+    /// BIP32/BIP44 derivation is only defined over secp256k1.
+    pub fn from_extended_key(extended: &hd::ExtendedKey) -> Self {
+        Self::Secp256k1(extended.signing_key())
+    }
+
+    pub fn to_pkcs8_der(&self) -> pkcs8::SecretDocument {
+        match self {
+            Self::Secp256k1(key) => key
+                .to_pkcs8_der()
+                .expect("a validly-constructed secp256k1 key always encodes"),
+            Self::Ed25519(key) => key
+                .to_pkcs8_der()
+                .expect("a validly-constructed ed25519 key always encodes"),
+        }
+    }
+
+    pub fn to_pkcs8_pem(&self) -> pkcs8::der::zeroize::Zeroizing<String> {
+        match self {
+            Self::Secp256k1(key) => key
+                .to_pkcs8_pem(LineEnding::LF)
+                .expect("a validly-constructed secp256k1 key always encodes"),
+            Self::Ed25519(key) => key
+                .to_pkcs8_pem(LineEnding::LF)
+                .expect("a validly-constructed ed25519 key always encodes"),
+        }
+    }
+
+    pub fn curve(&self) -> KeyCurve {
+        match self {
+            Self::Secp256k1(_) => KeyCurve::Secp256k1,
+            Self::Ed25519(_) => KeyCurve::Ed25519,
+        }
+    }
+
+    /// Signs a message's [`zeropb_sighash::SignMessage::sign_hash`].
+    /// secp256k1 uses RFC 6979 deterministic ECDSA so signing the same
+    /// hash twice always produces the same signature, matching the rest
+    /// of this workspace's determinism requirements.
+    pub fn sign_hash(&self, sign_hash: [u8; 32]) -> Signature {
+        match self {
+            Self::Secp256k1(key) => {
+                let (signature, _recovery_id): (k256::ecdsa::Signature, _) =
+                    k256::ecdsa::signature::hazmat::PrehashSigner::sign_prehash(key, &sign_hash)
+                        .expect("signing a 32-byte prehash cannot fail");
+                Signature::Secp256k1(signature)
+            }
+            Self::Ed25519(key) => {
+                use ed25519_dalek::Signer;
+                Signature::Ed25519(key.sign(&sign_hash))
+            }
+        }
+    }
+}
+
+pub enum Signature {
+    Secp256k1(k256::ecdsa::Signature),
+    Ed25519(ed25519_dalek::Signature),
+}
+
+impl Signature {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Secp256k1(sig) => sig.to_bytes().to_vec(),
+            Self::Ed25519(sig) => sig.to_bytes().to_vec(),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Generates the canonical decomposition, combining-class, and
+//! composition tables used by `src/lib.rs` from the Unicode Character
+//! Database, so the crate needs no runtime dependency on an external
+//! Unicode library and stays reproducible across Unicode versions by
+//! pinning the two data files below.
+//!
+//! Re-run by placing a fresh `UnicodeData.txt` / `CompositionExclusions.txt`
+//! (from <https://www.unicode.org/Public/UCD/latest/ucd/>) under `data/`.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    println!("cargo:rerun-if-changed=data/UnicodeData.txt");
+    println!("cargo:rerun-if-changed=data/CompositionExclusions.txt");
+
+    let unicode_data = fs::read_to_string("data/UnicodeData.txt")
+        .expect("data/UnicodeData.txt missing; see build.rs header for how to fetch it");
+    let exclusions_data = fs::read_to_string("data/CompositionExclusions.txt")
+        .expect("data/CompositionExclusions.txt missing; see build.rs header for how to fetch it");
+
+    let exclusions = parse_exclusions(&exclusions_data);
+    let (decompositions, ccc) = parse_unicode_data(&unicode_data);
+    let compositions = derive_compositions(&decompositions, &ccc, &exclusions);
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let dest = Path::new(&out_dir).join("nfc_tables.rs");
+    fs::write(dest, render(&decompositions, &ccc, &compositions)).unwrap();
+}
+
+/// `code point -> canonical decomposition mapping`. Compatibility
+/// decompositions (tagged, e.g. `<font>`) are skipped; NFC only cares
+/// about canonical decomposition.
+fn parse_unicode_data(data: &str) -> (BTreeMap<u32, Vec<u32>>, BTreeMap<u32, u8>) {
+    let mut decompositions = BTreeMap::new();
+    let mut ccc = BTreeMap::new();
+
+    for line in data.lines() {
+        let fields: Vec<&str> = line.split(';').collect();
+        if fields.len() < 6 {
+            continue;
+        }
+        let cp = u32::from_str_radix(fields[0], 16).unwrap();
+
+        let combining_class: u8 = fields[3].parse().unwrap_or(0);
+        if combining_class != 0 {
+            ccc.insert(cp, combining_class);
+        }
+
+        let decomposition = fields[5];
+        if decomposition.is_empty() || decomposition.starts_with('<') {
+            continue;
+        }
+        let mapped: Vec<u32> = decomposition
+            .split_whitespace()
+            .map(|s| u32::from_str_radix(s, 16).unwrap())
+            .collect();
+        decompositions.insert(cp, mapped);
+    }
+
+    (decompositions, ccc)
+}
+
+fn parse_exclusions(data: &str) -> Vec<u32> {
+    data.lines()
+        .filter_map(|line| line.split('#').next())
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| u32::from_str_radix(line, 16).unwrap())
+        .collect()
+}
+
+/// Inverts the one-to-many decomposition table into the one-to-one
+/// `(starter, combiner) -> composite` pairs used by canonical
+/// composition, dropping anything on the composition-exclusion list as
+/// well as singleton and non-starter decompositions (those whose mapping
+/// starts with a character of nonzero combining class).
+fn derive_compositions(
+    decompositions: &BTreeMap<u32, Vec<u32>>,
+    ccc: &BTreeMap<u32, u8>,
+    exclusions: &[u32],
+) -> BTreeMap<(u32, u32), u32> {
+    let mut compositions = BTreeMap::new();
+    for (&composite, mapping) in decompositions {
+        if exclusions.contains(&composite)
+            || mapping.len() != 2
+            || ccc.contains_key(&mapping[0])
+        {
+            continue;
+        }
+        compositions.insert((mapping[0], mapping[1]), composite);
+    }
+    compositions
+}
+
+fn render(
+    decompositions: &BTreeMap<u32, Vec<u32>>,
+    ccc: &BTreeMap<u32, u8>,
+    compositions: &BTreeMap<(u32, u32), u32>,
+) -> String {
+    let mut out = String::new();
+    out.push_str("// @generated by build.rs from the Unicode Character Database. Do not edit.\n\n");
+
+    writeln!(
+        out,
+        "pub(crate) static DECOMPOSITION: &[(u32, &[u32])] = &["
+    )
+    .unwrap();
+    for (cp, mapping) in decompositions {
+        writeln!(out, "    ({cp:#x}, &{mapping:#x?}),").unwrap();
+    }
+    out.push_str("];\n\n");
+
+    writeln!(out, "pub(crate) static CCC: &[(u32, u8)] = &[").unwrap();
+    for (cp, class) in ccc {
+        writeln!(out, "    ({cp:#x}, {class}),").unwrap();
+    }
+    out.push_str("];\n\n");
+
+    writeln!(
+        out,
+        "pub(crate) static COMPOSITION: &[(u32, u32, u32)] = &["
+    )
+    .unwrap();
+    for (&(a, b), composite) in compositions {
+        writeln!(out, "    ({a:#x}, {b:#x}, {composite:#x}),").unwrap();
+    }
+    out.push_str("];\n");
+
+    out
+}
@@ -0,0 +1,247 @@
+//! Self-contained Unicode NFC (Normalization Form C) for `string` fields
+//! generated by `zeropb_build`.
+//!
+//! Cosmos consensus needs byte-for-byte determinism across validators, but
+//! arbitrary UTF-8 lets two validators disagree on whether a precomposed
+//! character (`"\u{e9}"`) and its combining-mark decomposition
+//! (`"e\u{301}"`) are the "same" denom. Generated `string` accessors are
+//! expected to call [`normalize`] on write, or [`is_canonical`] to reject
+//! non-canonical input on read, per the codegen mode configured for the
+//! field (see `zeropb_build`'s string-field options).
+//!
+//! The decomposition/combining-class/composition tables are generated at
+//! build time from the Unicode Character Database (see `build.rs`) so this
+//! crate carries no runtime dependency on an external Unicode library.
+//! Hangul syllables are handled algorithmically per UAX #15 rather than
+//! being present in the generated tables, since they decompose/compose by
+//! arithmetic on the syllable index.
+
+include!(concat!(env!("OUT_DIR"), "/nfc_tables.rs"));
+
+// Hangul algorithmic constants, UAX #15 section 16.
+const S_BASE: u32 = 0xAC00;
+const L_BASE: u32 = 0x1100;
+const V_BASE: u32 = 0x1161;
+const T_BASE: u32 = 0x11A7;
+const L_COUNT: u32 = 19;
+const V_COUNT: u32 = 21;
+const T_COUNT: u32 = 28;
+const N_COUNT: u32 = V_COUNT * T_COUNT;
+const S_COUNT: u32 = L_COUNT * N_COUNT;
+
+/// Normalizes `input` to NFC and returns it as an owned `String`.
+///
+/// Idempotent: normalizing an already-NFC string returns it unchanged.
+pub fn normalize(input: &str) -> String {
+    let decomposed = decompose(input);
+    let reordered = canonical_order(decomposed);
+    compose(reordered)
+}
+
+/// Returns `true` if `input` is already in NFC, i.e. `normalize(input) ==
+/// input`, for callers that only need a yes/no answer (e.g. rejecting
+/// non-canonical input on read instead of silently rewriting it). It
+/// runs a full [`normalize`] and compares, so it costs the same.
+pub fn is_canonical(input: &str) -> bool {
+    // A streaming quick-check could short-circuit most ASCII-only inputs
+    // without allocating; not worth the complexity until profiling shows
+    // this path is hot.
+    normalize(input) == input
+}
+
+/// Step 1: canonical decomposition. Recursively replaces every code point
+/// by its canonical decomposition mapping until reaching a fixpoint of
+/// characters with no further canonical decomposition, decomposing
+/// Hangul syllables algorithmically.
+fn decompose(input: &str) -> Vec<u32> {
+    let mut out = Vec::with_capacity(input.len());
+    for ch in input.chars() {
+        decompose_one(ch as u32, &mut out);
+    }
+    out
+}
+
+fn decompose_one(cp: u32, out: &mut Vec<u32>) {
+    if let Some((l, v, t)) = hangul_decompose(cp) {
+        out.push(l);
+        out.push(v);
+        if let Some(t) = t {
+            out.push(t);
+        }
+        return;
+    }
+    if let Ok(idx) = DECOMPOSITION.binary_search_by_key(&cp, |&(c, _)| c) {
+        for &mapped in DECOMPOSITION[idx].1 {
+            decompose_one(mapped, out);
+        }
+        return;
+    }
+    out.push(cp);
+}
+
+/// Decomposes a Hangul syllable into its leading/vowel/(optional trailing)
+/// jamo. An LV syllable (no final consonant, e.g. "가" GA) has no
+/// trailing jamo at all -- it must decompose to exactly two code points,
+/// not three with a padding `0`, since `0` is not a valid placeholder code
+/// point and would otherwise be recomposed as a literal NUL.
+fn hangul_decompose(cp: u32) -> Option<(u32, u32, Option<u32>)> {
+    if cp < S_BASE || cp >= S_BASE + S_COUNT {
+        return None;
+    }
+    let s_index = cp - S_BASE;
+    let l = L_BASE + s_index / N_COUNT;
+    let v = V_BASE + (s_index % N_COUNT) / T_COUNT;
+    let t_index = s_index % T_COUNT;
+    if t_index == 0 {
+        Some((l, v, None))
+    } else {
+        Some((l, v, Some(T_BASE + t_index)))
+    }
+}
+
+fn combining_class(cp: u32) -> u8 {
+    CCC.binary_search_by_key(&cp, |&(c, _)| c)
+        .map(|idx| CCC[idx].1)
+        .unwrap_or(0)
+}
+
+/// Step 2: canonical ordering. Within every maximal run of characters
+/// with nonzero combining class, stable-sort by combining class so
+/// equivalent combining-mark orderings converge to the same sequence.
+fn canonical_order(mut codepoints: Vec<u32>) -> Vec<u32> {
+    let mut i = 0;
+    while i < codepoints.len() {
+        if combining_class(codepoints[i]) == 0 {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < codepoints.len() && combining_class(codepoints[i]) != 0 {
+            i += 1;
+        }
+        codepoints[start..i].sort_by_key(|&cp| combining_class(cp));
+    }
+    codepoints
+}
+
+/// Step 3: canonical composition. Walking from the most recent starter
+/// (combining class 0), attempts to compose each following character with
+/// the starter via the generated composition table (or the Hangul L+V /
+/// LV+T algorithmic rules), unless composition is *blocked*: some
+/// character between the starter and the candidate has a combining class
+/// greater than or equal to the candidate's.
+fn compose(codepoints: Vec<u32>) -> String {
+    if codepoints.is_empty() {
+        return String::new();
+    }
+
+    let mut result: Vec<u32> = vec![codepoints[0]];
+    let mut starter_idx = 0usize;
+    let mut last_ccc = combining_class(codepoints[0]);
+
+    for &cp in &codepoints[1..] {
+        let ccc = combining_class(cp);
+        let starter = result[starter_idx];
+        // Blocking is a property of the character *between* the starter
+        // and the candidate, not of the candidate itself: if the
+        // immediately preceding character is the starter (`last_ccc ==
+        // 0`, i.e. there is no intervening character at all, as with
+        // adjacent Hangul L+V), composition is never blocked regardless
+        // of the candidate's own combining class. Only a genuine
+        // intervening combining mark (`last_ccc != 0`) with a combining
+        // class at or above the candidate's blocks composition.
+        let blocked = last_ccc != 0 && last_ccc >= ccc;
+
+        let composite = if blocked {
+            None
+        } else {
+            hangul_compose(starter, cp).or_else(|| composition_pair(starter, cp))
+        };
+
+        match composite {
+            Some(composite) => {
+                result[starter_idx] = composite;
+                // The starter was just replaced by its composite; combining
+                // class of a composite starter is always 0 (UAX #15 stays
+                // within this derivation because excluded singletons/the
+                // full composition exclusion list are already filtered out
+                // of the generated table).
+                last_ccc = 0;
+            }
+            None => {
+                result.push(cp);
+                if ccc == 0 {
+                    starter_idx = result.len() - 1;
+                }
+                last_ccc = ccc;
+            }
+        }
+    }
+
+    result.into_iter().filter_map(char::from_u32).collect()
+}
+
+fn composition_pair(a: u32, b: u32) -> Option<u32> {
+    COMPOSITION
+        .binary_search_by(|&(x, y, _)| (x, y).cmp(&(a, b)))
+        .ok()
+        .map(|idx| COMPOSITION[idx].2)
+}
+
+fn hangul_compose(a: u32, b: u32) -> Option<u32> {
+    // L + V -> LV
+    if (L_BASE..L_BASE + L_COUNT).contains(&a) && (V_BASE..V_BASE + V_COUNT).contains(&b) {
+        let l_index = a - L_BASE;
+        let v_index = b - V_BASE;
+        return Some(S_BASE + (l_index * V_COUNT + v_index) * T_COUNT);
+    }
+    // LV + T -> LVT
+    if (S_BASE..S_BASE + S_COUNT).contains(&a)
+        && (a - S_BASE) % T_COUNT == 0
+        && (T_BASE + 1..T_BASE + T_COUNT).contains(&b)
+    {
+        return Some(a + (b - T_BASE));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Regression test for a blocking-guard bug: the guard used to key off
+    // the *candidate*'s combining class instead of the *preceding
+    // character*'s, so a starter-shaped candidate (combining class 0)
+    // following an intervening combining mark was never blocked and got
+    // wrongly composed with a starter further back.
+    //
+    // U+0B94 (TAMIL LETTER AU) canonically decomposes to <U+0B92, U+0BD7>,
+    // both of which have combining class 0. With a genuine combining mark
+    // (U+0300, class 230) between them, composition must be blocked:
+    // U+0300 has nonzero combining class, so per UAX #15 it is a real
+    // intervening character and U+0B92/U+0BD7 must not recompose around it.
+    #[test]
+    fn compose_blocked_by_intervening_combining_mark() {
+        let input = "\u{0B92}\u{0300}\u{0BD7}";
+        let normalized = normalize(input);
+        assert_ne!(
+            normalized, "\u{0B94}\u{0300}",
+            "composition must not skip over an intervening combining mark"
+        );
+        assert_eq!(
+            normalized, input,
+            "no canonical decomposition/ordering/composition applies here, so the \
+             string should pass through unchanged"
+        );
+    }
+
+    // Adjacent Hangul L+V (no intervening character at all) must still
+    // compose: `last_ccc == 0` here means "the previous code point is the
+    // starter itself", not "there is a blocking character of class 0".
+    #[test]
+    fn compose_adjacent_starters_not_blocked() {
+        // U+1100 (HANGUL CHOSEONG KIYEOK) + U+1161 (HANGUL JUNGSEONG A) -> U+AC00 (가)
+        let input = "\u{1100}\u{1161}";
+        assert_eq!(normalize(input), "\u{AC00}");
+    }
+}
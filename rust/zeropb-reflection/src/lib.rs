@@ -0,0 +1,111 @@
+//! Runtime descriptor pool and gRPC Server Reflection for the
+//! `FileDescriptorProto` bytes every module registers via
+//! `zeropb_build::compile_fd_bytes` in its `build.rs`.
+//!
+//! Today those bytes are only used at compile time to drive the zero-copy
+//! codegen; nothing aggregates them across modules at runtime. This crate
+//! adds that aggregation, so tools like `grpcurl` can introspect a node
+//! built from these modules, and a type URL such as
+//! `/cosmos.bank.v1beta1.MsgSend` pulled out of an `Any` can be resolved
+//! and decoded by a client with no compile-time knowledge of the concrete
+//! type -- the same descriptor-bin aggregation `buf`/`protoc`'s
+//! `--descriptor_set_out` produces for upstream proto build pipelines.
+
+use prost_reflect::DescriptorPool as ReflectPool;
+use std::sync::OnceLock;
+
+/// Aggregates every module's `FileDescriptorProto` bytes into a single
+/// pool that resolves a type URL to a [`prost_reflect::MessageDescriptor`]
+/// and decodes the matching bytes into a [`prost_reflect::DynamicMessage`].
+#[derive(Clone)]
+pub struct DescriptorPool {
+    inner: ReflectPool,
+}
+
+impl DescriptorPool {
+    /// Builds a pool from every `FileDescriptorProto` registered by
+    /// `zeropb_build::compile_fd_bytes` across all linked modules.
+    ///
+    /// Panics if a registered file fails to decode as a
+    /// `FileDescriptorProto`, or if a registered file's dependencies are
+    /// not all registered before it (files are added one at a time, in
+    /// registration order) -- both indicate a bug in a module's
+    /// `build.rs`, not a runtime condition callers should handle.
+    pub fn from_registered_modules() -> Self {
+        let mut inner = ReflectPool::new();
+        for bytes in zeropb::registry::all_file_descriptor_bytes() {
+            let file = prost::Message::decode(bytes)
+                .expect("module registered bytes that are not a valid FileDescriptorProto");
+            inner
+                .add_file_descriptor_proto(file)
+                .expect("module's FileDescriptorProto dependency set is not closed");
+        }
+        Self { inner }
+    }
+
+    /// Resolves a type URL such as `/cosmos.bank.v1beta1.MsgSend` or
+    /// `type.googleapis.com/cosmos.bank.v1beta1.MsgSend` to its message
+    /// descriptor.
+    pub fn resolve_type_url(&self, type_url: &str) -> Option<prost_reflect::MessageDescriptor> {
+        let full_name = type_url.rsplit('/').next().unwrap_or(type_url);
+        self.inner.get_message_by_name(full_name)
+    }
+
+    /// Decodes `bytes` (the `value` of a `google.protobuf.Any`) as the
+    /// message named by `type_url`.
+    pub fn decode_any(
+        &self,
+        type_url: &str,
+        bytes: &[u8],
+    ) -> Result<prost_reflect::DynamicMessage, DecodeAnyError> {
+        let descriptor = self
+            .resolve_type_url(type_url)
+            .ok_or_else(|| DecodeAnyError::UnknownType(type_url.to_string()))?;
+        prost_reflect::DynamicMessage::decode(descriptor, bytes)
+            .map_err(DecodeAnyError::Decode)
+    }
+
+    /// The underlying `prost-reflect` pool, for callers (like
+    /// [`reflection_service`]) that need the full `prost_reflect` API.
+    pub fn as_reflect_pool(&self) -> &ReflectPool {
+        &self.inner
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DecodeAnyError {
+    #[error("no registered module exposes a descriptor for type URL {0:?}")]
+    UnknownType(String),
+    #[error("failed to decode message body: {0}")]
+    Decode(#[source] prost::DecodeError),
+}
+
+static GLOBAL_POOL: OnceLock<DescriptorPool> = OnceLock::new();
+
+/// The process-wide descriptor pool, built once from every module
+/// registered at the time of first use.
+pub fn global() -> &'static DescriptorPool {
+    GLOBAL_POOL.get_or_init(DescriptorPool::from_registered_modules)
+}
+
+/// Builds a standard gRPC Server Reflection service covering every message
+/// and service in [`global`], ready to be added to a `tonic` server
+/// alongside the node's other services. Only `grpc.reflection.v1alpha` is
+/// served; the `grpc.reflection.v1` service is not included.
+pub fn reflection_service(
+) -> tonic_reflection::server::v1alpha::ServerReflectionServer<impl tonic_reflection::server::v1alpha::ServerReflection>
+{
+    let file_descriptor_set = prost_types::FileDescriptorSet {
+        file: global()
+            .as_reflect_pool()
+            .files()
+            .map(|file| file.file_descriptor_proto().as_ref().clone())
+            .collect(),
+    };
+    tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(&prost::Message::encode_to_vec(
+            &file_descriptor_set,
+        ))
+        .build()
+        .expect("aggregated module descriptor set failed to build a reflection service")
+}
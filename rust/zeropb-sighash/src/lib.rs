@@ -0,0 +1,114 @@
+//! Domain-separated canonical sign hashes for generated messages.
+//!
+//! Hashing the raw wire bytes of a message (as `SIGN_MODE_DIRECT` does)
+//! means two semantically-identical messages can hash differently if one
+//! encoder reorders fields or uses a different varint form, and two
+//! *different* message types whose encodings happen to collide hash the
+//! same. This module instead builds the hash structurally, field by
+//! field, under a BLAKE2b personalization tag derived from the message's
+//! fully-qualified proto name, the same way upgrade-tagged transaction
+//! sighashing binds a signature to a specific version/purpose rather than
+//! to an undifferentiated byte string.
+//!
+//! Repeated fields (the `amount` `Coin` list on `MsgSend`, `inputs` and
+//! `outputs` on `MsgMultiSend`) are hashed into their own intermediate
+//! digest first, via [`hash_repeated`], before being folded into the
+//! top-level hash. That lets a wallet recompute just the sub-digest for
+//! the part of the message it wants to show the user (e.g. a coin
+//! breakdown) without re-hashing the whole message, while the final
+//! `sign_hash` stays bound to the exact message type and field structure.
+
+use blake2b_simd::Params;
+
+/// BLAKE2b's `personal` parameter is fixed at exactly 16 bytes.
+const PERSONAL_LEN: usize = 16;
+
+/// Derives the BLAKE2b personalization tag for `full_name`.
+///
+/// This hashes `full_name` down to 16 bytes rather than truncating it:
+/// fully-qualified proto names routinely share a package prefix longer
+/// than 16 bytes (`cosmos.bank.v1beta1.` alone is 20), so a raw prefix
+/// would put e.g. `cosmos.bank.v1beta1.MsgSend` and
+/// `cosmos.bank.v1beta1.MsgMultiSend` under the same domain tag, and two
+/// differently-shaped messages hashing under the same domain is exactly
+/// the cross-type collision this scheme exists to prevent. Hashing
+/// absorbs the whole name, however long, before truncating.
+pub fn personalization(full_name: &str) -> [u8; PERSONAL_LEN] {
+    let digest = blake2b_simd::Params::new()
+        .hash_length(PERSONAL_LEN)
+        .to_state()
+        .update(full_name.as_bytes())
+        .finalize();
+    let mut tag = [0u8; PERSONAL_LEN];
+    tag.copy_from_slice(digest.as_bytes());
+    tag
+}
+
+/// Starts a 32-byte BLAKE2b hash personalized for `domain` (a
+/// fully-qualified proto name, or a proto name joined with a field name
+/// for a repeated-field sub-digest).
+fn domain_hasher(domain: &str) -> blake2b_simd::State {
+    Params::new()
+        .hash_length(32)
+        .personal(&personalization(domain))
+        .to_state()
+}
+
+fn finalize(hasher: blake2b_simd::State) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out.copy_from_slice(hasher.finalize().as_bytes());
+    out
+}
+
+/// Implemented by every generated message that supports signing.
+/// Generated `impl`s are produced by `zeropb_build` alongside the message
+/// struct itself, in field-number order.
+pub trait SignMessage {
+    /// Fully-qualified proto name, e.g. `cosmos.bank.v1beta1.MsgSend`.
+    const FULL_NAME: &'static str;
+
+    /// Computes the deterministic sign hash for this message.
+    fn sign_hash(&self) -> [u8; 32] {
+        let mut hasher = domain_hasher(Self::FULL_NAME);
+        self.write_hashed_fields(&mut hasher);
+        finalize(hasher)
+    }
+
+    /// Feeds this message's fields into `hasher` in proto field-number
+    /// order: scalar and `string`/`bytes` fields are fed directly, and
+    /// every repeated message field is first reduced to a sub-digest via
+    /// [`hash_repeated`] and the sub-digest is fed in its place.
+    fn write_hashed_fields(&self, hasher: &mut blake2b_simd::State);
+}
+
+/// Hashes a repeated field's elements into one sub-digest, by
+/// concatenating the canonically-ordered element digests under a domain
+/// tag scoped to `{full_name}.{field_name}` (so e.g. `MsgSend.amount` and
+/// `MsgMultiSend.inputs` can't be swapped even if they happened to hash
+/// the same sequence of `Coin`s). Each element digest is normally that
+/// element's own [`SignMessage::sign_hash`].
+pub fn hash_repeated(full_name: &str, field_name: &str, element_digests: &[[u8; 32]]) -> [u8; 32] {
+    let domain = format!("{full_name}.{field_name}");
+    let mut hasher = domain_hasher(&domain);
+    for digest in element_digests {
+        hasher.update(digest);
+    }
+    finalize(hasher)
+}
+
+/// Verification support for wallets that only have a breakdown of a
+/// message's repeated fields (as precomputed sub-digests) rather than the
+/// decoded elements themselves, e.g. because they rendered each `Coin` as
+/// it streamed in and want to confirm the final signature without
+/// re-decoding. `write_scalar_and_sub_digest_fields` must write the
+/// fields in the same field-number
+/// order `write_hashed_fields` would have written them in, with each
+/// repeated field already replaced by its sub-digest.
+pub fn sign_hash_from_sub_digests(
+    full_name: &str,
+    write_scalar_and_sub_digest_fields: impl FnOnce(&mut blake2b_simd::State),
+) -> [u8; 32] {
+    let mut hasher = domain_hasher(full_name);
+    write_scalar_and_sub_digest_fields(&mut hasher);
+    finalize(hasher)
+}
@@ -0,0 +1,405 @@
+//! ADR-050 `SIGN_MODE_TEXTUAL` rendering for hardware wallets.
+//!
+//! A hardware wallet has no way to parse an arbitrary proto message, so
+//! `SIGN_MODE_DIRECT`'s raw bytes are useless on-device: the wallet needs
+//! a flat, ordered list of human-readable [`Screen`]s it can page through
+//! and have the user confirm, and the signature must cover exactly those
+//! screens so what's displayed is what's signed. This module walks a
+//! decoded message field by field to build that screen list, then
+//! canonically CBOR-encodes it; the encoded bytes are the sign-bytes.
+//!
+//! Determinism is the load-bearing invariant here: if two semantically
+//! identical messages produced different screens (or the same screens
+//! encoded to different bytes), the signature computed on one device
+//! would not verify against a render on another.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+/// One line (or sub-line) of a textual rendering.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Screen {
+    pub title: String,
+    pub content: String,
+    /// Nesting depth: incremented for fields of a nested message,
+    /// constant across elements of a repeated field.
+    pub indent: u8,
+    /// Hidden unless the wallet is in expert mode, e.g. raw field values
+    /// that are redundant with a friendlier rendering shown alongside
+    /// them (a `Coin`'s base-unit amount next to its display-unit
+    /// rendering).
+    pub expert: bool,
+}
+
+impl Screen {
+    fn new(title: impl Into<String>, content: impl Into<String>, indent: u8) -> Self {
+        Self {
+            title: title.into(),
+            content: content.into(),
+            indent,
+            expert: false,
+        }
+    }
+
+    fn expert(mut self) -> Self {
+        self.expert = true;
+        self
+    }
+}
+
+/// Renders a single field's value into zero or more screens. The default
+/// [`render_message`] renderer covers every scalar/message/enum/timestamp/
+/// bytes field generically; types that need a friendlier rendering (e.g.
+/// [`CoinRenderer`] converting base units to display units) plug in via
+/// [`MessageRenderer`] and a [`RendererRegistry`] instead of being
+/// special-cased in the walker.
+pub trait ValueRenderer<T: ?Sized> {
+    fn render(&self, ctx: &RenderContext<'_>, title: &str, indent: u8, value: &T) -> Vec<Screen>;
+}
+
+/// Renders a nested message of a specific type, looked up by
+/// fully-qualified proto name via [`RendererRegistry`]. This is the
+/// object-safe entry point `render_field` dispatches to for a `Message`
+/// field, before falling back to the generic field walk; [`CoinRenderer`]
+/// implements this by adapting [`zeropb::DynMessageView`]'s fields into
+/// the [`CoinValue`] its [`ValueRenderer`] impl expects.
+pub trait MessageRenderer {
+    fn render(
+        &self,
+        ctx: &RenderContext<'_>,
+        registry: &RendererRegistry,
+        title: &str,
+        indent: u8,
+        message: &dyn zeropb::DynMessageView,
+    ) -> Vec<Screen>;
+}
+
+/// Maps a message's fully-qualified proto name (e.g.
+/// `cosmos.base.v1beta1.Coin`) to the [`MessageRenderer`] that should
+/// render it. `render_field` consults this for every `Message` field
+/// before falling back to the generic recursive walk, which is how a
+/// `Coin` nested three levels deep in a repeated field still renders as
+/// `1 ATOM` instead of a raw `denom`/`amount` pair.
+#[derive(Default)]
+pub struct RendererRegistry {
+    by_full_name: HashMap<&'static str, Box<dyn MessageRenderer>>,
+}
+
+impl RendererRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(
+        &mut self,
+        full_name: &'static str,
+        renderer: impl MessageRenderer + 'static,
+    ) -> &mut Self {
+        self.by_full_name.insert(full_name, Box::new(renderer));
+        self
+    }
+
+    fn get(&self, full_name: &str) -> Option<&dyn MessageRenderer> {
+        self.by_full_name.get(full_name).map(Box::as_ref)
+    }
+}
+
+/// The registry bank messages render with: just [`CoinRenderer`] for
+/// `cosmos.base.v1beta1.Coin` today, but the registration point other
+/// specialized renderers (e.g. an `Any`-aware renderer) plug into as
+/// `SIGN_MODE_TEXTUAL` grows beyond bank messages.
+pub fn bank_renderer_registry() -> RendererRegistry {
+    let mut registry = RendererRegistry::new();
+    registry.register("cosmos.base.v1beta1.Coin", CoinRenderer);
+    registry
+}
+
+/// Shared lookups a [`ValueRenderer`] needs that aren't available from the
+/// field value alone, e.g. bank denom metadata to convert a `Coin`'s base
+/// units to its display unit.
+pub struct RenderContext<'a> {
+    pub denom_metadata: &'a dyn DenomMetadataProvider,
+}
+
+/// Looks up a denom's display metadata (exponent and display denom) so a
+/// `Coin` can be rendered in human-scale units instead of base units.
+pub trait DenomMetadataProvider {
+    /// Returns `(display_denom, exponent)`, e.g. `("ATOM", 6)` for
+    /// `uatom`, if `base_denom` has registered metadata.
+    fn display_unit(&self, base_denom: &str) -> Option<(String, u32)>;
+}
+
+/// Renders a bank `Coin` (`denom`, `amount` in base units) as a
+/// display-unit screen, e.g. `1000000uatom` -> `1 ATOM`, followed by an
+/// expert-only screen with the raw base-unit rendering; when no denom
+/// metadata is registered, only the raw base-unit screen is produced.
+pub struct CoinRenderer;
+
+/// Minimal view of a `Coin` the renderer needs; [`CoinRenderer`]'s
+/// [`MessageRenderer`] impl builds one from a message's `denom` and
+/// `amount` string fields.
+pub struct CoinValue<'a> {
+    pub denom: &'a str,
+    pub amount: &'a str,
+}
+
+impl ValueRenderer<CoinValue<'_>> for CoinRenderer {
+    fn render(
+        &self,
+        ctx: &RenderContext<'_>,
+        title: &str,
+        indent: u8,
+        coin: &CoinValue<'_>,
+    ) -> Vec<Screen> {
+        let mut screens = Vec::with_capacity(2);
+        match ctx.denom_metadata.display_unit(coin.denom) {
+            Some((display_denom, exponent)) => {
+                let display_amount = shift_decimal(coin.amount, exponent);
+                screens.push(Screen::new(
+                    title,
+                    format!("{display_amount} {display_denom}"),
+                    indent,
+                ));
+                screens.push(
+                    Screen::new(
+                        title,
+                        format!("{}{}", coin.amount, coin.denom),
+                        indent,
+                    )
+                    .expert(),
+                );
+            }
+            None => {
+                screens.push(Screen::new(
+                    title,
+                    format!("{}{}", coin.amount, coin.denom),
+                    indent,
+                ));
+            }
+        }
+        screens
+    }
+}
+
+impl MessageRenderer for CoinRenderer {
+    fn render(
+        &self,
+        ctx: &RenderContext<'_>,
+        registry: &RendererRegistry,
+        title: &str,
+        indent: u8,
+        message: &dyn zeropb::DynMessageView,
+    ) -> Vec<Screen> {
+        match (find_str_field(message, "denom"), find_str_field(message, "amount")) {
+            (Some(denom), Some(amount)) => {
+                ValueRenderer::render(self, ctx, title, indent, &CoinValue { denom, amount })
+            }
+            // A `Coin` missing either field isn't actually a `Coin` this
+            // renderer knows how to special-case (e.g. a future proto
+            // revision renamed a field); fall back to the generic walk
+            // rather than silently dropping it from the signed screens.
+            _ => {
+                let mut screens = vec![Screen::new(title, "", indent)];
+                screens.extend(render_message_dyn(ctx, registry, message, indent + 1));
+                screens
+            }
+        }
+    }
+}
+
+/// Finds a top-level `string` field named `field_name` on a dynamic
+/// message view. Used to adapt [`zeropb::DynMessageView`]'s generic field
+/// list into the concrete fields a [`MessageRenderer`] like [`CoinRenderer`]
+/// expects, without needing the nested message's concrete Rust type.
+fn find_str_field<'a>(message: &'a dyn zeropb::DynMessageView, field_name: &str) -> Option<&'a str> {
+    message.fields().into_iter().find_map(|field| {
+        if field.name != field_name {
+            return None;
+        }
+        match field.value {
+            zeropb::FieldValue::Scalar(zeropb::ScalarValue::Str(s)) => Some(s),
+            _ => None,
+        }
+    })
+}
+
+/// Renders `base_units` (a base-10 integer string) shifted left by
+/// `exponent` decimal places, e.g. `shift_decimal("1000000", 6) == "1"`
+/// and `shift_decimal("1500000", 6) == "1.5"`.
+fn shift_decimal(base_units: &str, exponent: u32) -> String {
+    let exponent = exponent as usize;
+    if base_units.len() <= exponent {
+        let mut out = String::from("0.");
+        for _ in 0..(exponent - base_units.len()) {
+            out.push('0');
+        }
+        out.push_str(base_units);
+        trim_trailing_zeros(&out)
+    } else {
+        let split = base_units.len() - exponent;
+        if exponent == 0 {
+            base_units.to_string()
+        } else {
+            let mut out = String::with_capacity(base_units.len() + 1);
+            out.push_str(&base_units[..split]);
+            out.push('.');
+            out.push_str(&base_units[split..]);
+            trim_trailing_zeros(&out)
+        }
+    }
+}
+
+fn trim_trailing_zeros(decimal: &str) -> String {
+    if !decimal.contains('.') {
+        return decimal.to_string();
+    }
+    let trimmed = decimal.trim_end_matches('0');
+    let trimmed = trimmed.trim_end_matches('.');
+    if trimmed.is_empty() {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Renders a decoded message into its flat screen list, walking fields in
+/// proto field-number order. A nested message whose fully-qualified proto
+/// name is registered in `registry` (e.g. `cosmos.base.v1beta1.Coin` ->
+/// [`CoinRenderer`]) is rendered by that renderer instead of generically;
+/// anything else recurses with its fields at `indent + 1`. Each element of
+/// a repeated field gets its own index suffix appended to the field's
+/// title (`coins[0]`, `coins[1]`, ...).
+pub fn render_message<M: zeropb::MessageView>(
+    ctx: &RenderContext<'_>,
+    registry: &RendererRegistry,
+    message: &M,
+    indent: u8,
+) -> Vec<Screen> {
+    let mut screens = Vec::new();
+    for field in message.fields() {
+        render_field(ctx, registry, &field, indent, &mut screens);
+    }
+    screens
+}
+
+/// Same as [`render_message`], but for a nested message reached only
+/// dynamically (via [`zeropb::DynMessageView`]) rather than through a
+/// statically-typed [`zeropb::MessageView`] impl -- i.e. every message
+/// below the top level.
+fn render_message_dyn(
+    ctx: &RenderContext<'_>,
+    registry: &RendererRegistry,
+    message: &dyn zeropb::DynMessageView,
+    indent: u8,
+) -> Vec<Screen> {
+    let mut screens = Vec::new();
+    for field in message.fields() {
+        render_field(ctx, registry, &field, indent, &mut screens);
+    }
+    screens
+}
+
+fn render_field(
+    ctx: &RenderContext<'_>,
+    registry: &RendererRegistry,
+    field: &zeropb::NamedField<'_>,
+    indent: u8,
+    out: &mut Vec<Screen>,
+) {
+    match &field.value {
+        zeropb::FieldValue::Scalar(scalar) => {
+            out.push(Screen::new(field.name.clone(), render_scalar(scalar), indent));
+        }
+        zeropb::FieldValue::Enum(value, name) => {
+            let rendered = name.map(str::to_string).unwrap_or_else(|| value.to_string());
+            out.push(Screen::new(field.name.clone(), rendered, indent));
+        }
+        zeropb::FieldValue::Timestamp(ts) => {
+            out.push(Screen::new(field.name.clone(), ts.to_rfc3339(), indent));
+        }
+        zeropb::FieldValue::Bytes(bytes) => {
+            out.push(Screen::new(field.name.clone(), hex_encode(bytes), indent));
+        }
+        zeropb::FieldValue::Message(nested) => match registry.get(nested.full_name()) {
+            Some(renderer) => {
+                out.extend(renderer.render(ctx, registry, &field.name, indent, nested.as_ref()));
+            }
+            None => {
+                out.push(Screen::new(field.name.clone(), "", indent));
+                out.extend(render_message_dyn(ctx, registry, nested.as_ref(), indent + 1));
+            }
+        },
+        zeropb::FieldValue::Repeated(items) => {
+            for (i, item) in items.iter().enumerate() {
+                let title = format!("{}[{i}]", field.name);
+                render_field(
+                    ctx,
+                    registry,
+                    &zeropb::NamedField {
+                        name: title,
+                        value: item.clone(),
+                    },
+                    indent,
+                    out,
+                );
+            }
+        }
+    }
+}
+
+fn render_scalar(scalar: &zeropb::ScalarValue) -> String {
+    match scalar {
+        zeropb::ScalarValue::Bool(b) => b.to_string(),
+        zeropb::ScalarValue::Int(i) => i.to_string(),
+        zeropb::ScalarValue::UInt(u) => u.to_string(),
+        zeropb::ScalarValue::Str(s) => s.to_string(),
+    }
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        write!(out, "{b:02x}").unwrap();
+    }
+    out
+}
+
+/// CBOR-encodes `screens` as the canonical `{1: [screens...]}` map that
+/// is the actual sign-bytes for `SIGN_MODE_TEXTUAL`. Each screen is
+/// itself a map with fixed integer keys (`1`: title, `2`: content, `3`:
+/// indent, `4`: expert) written in that order, and `expert` is omitted
+/// when `false` -- both choices are fixed ahead of time rather than
+/// derived from struct field order, so the encoding can't silently drift
+/// if [`Screen`]'s field order ever changes.
+pub fn encode_screens_cbor(screens: &[Screen]) -> Vec<u8> {
+    let screens_value = ciborium::Value::Array(screens.iter().map(screen_to_cbor).collect());
+    let document = ciborium::Value::Map(vec![(ciborium::Value::Integer(1.into()), screens_value)]);
+
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&document, &mut bytes).expect("in-memory CBOR encoding cannot fail");
+    bytes
+}
+
+fn screen_to_cbor(screen: &Screen) -> ciborium::Value {
+    let mut entries = vec![
+        (
+            ciborium::Value::Integer(1.into()),
+            ciborium::Value::Text(screen.title.clone()),
+        ),
+        (
+            ciborium::Value::Integer(2.into()),
+            ciborium::Value::Text(screen.content.clone()),
+        ),
+        (
+            ciborium::Value::Integer(3.into()),
+            ciborium::Value::Integer(screen.indent.into()),
+        ),
+    ];
+    if screen.expert {
+        entries.push((
+            ciborium::Value::Integer(4.into()),
+            ciborium::Value::Bool(true),
+        ));
+    }
+    ciborium::Value::Map(entries)
+}